@@ -0,0 +1,760 @@
+use crate::{warning, LineChartLog};
+use std::collections::HashMap;
+use svg::node::element::{self, path};
+use svg::{Document, Node};
+use tiny_skia::{Color, FillRule, Paint, PathBuilder, Pixmap, Stroke, Transform};
+
+/// Drawing primitives a chart can be rendered through, independent of the
+/// final file format. `render_chart` draws against this trait once; each
+/// implementor decides how to turn the calls into bytes.
+pub(crate) trait ChartBackend {
+    fn set_styles(&mut self, styles: &[String]);
+    fn line(&mut self, x1: f64, y1: f64, x2: f64, y2: f64, class: &str);
+    fn polyline(&mut self, points: &[(f64, f64)], class: &str);
+    fn path(&mut self, commands: Vec<path::Command>, class: &str);
+    fn text(&mut self, x: f64, y: f64, rotate: f64, text: &str, class: &str);
+    fn finish(&mut self, width: f64, height: f64) -> Vec<u8>;
+}
+
+/// Renders straight to an SVG `Document`, the tool's original output path.
+pub(crate) struct SvgBackend {
+    document: Document,
+}
+
+impl SvgBackend {
+    pub(crate) fn new() -> SvgBackend {
+        SvgBackend {
+            document: Document::new()
+                .set("xmlns", "http://www.w3.org/2000/svg")
+                .set("style", "background-color: white;"),
+        }
+    }
+
+    fn transform(x: f64, y: f64, rotate: f64) -> String {
+        if rotate == 0.0 {
+            format!("translate({},{})", x, y)
+        } else {
+            format!("translate({},{}) rotate({})", x, y, rotate)
+        }
+    }
+}
+
+impl ChartBackend for SvgBackend {
+    fn set_styles(&mut self, styles: &[String]) {
+        self.document.append(element::Style::new(styles.join("\n")));
+    }
+
+    fn line(&mut self, x1: f64, y1: f64, x2: f64, y2: f64, class: &str) {
+        self.document.append(
+            element::Path::new()
+                .set("class", class)
+                .set("d", path::Data::new().move_to((x1, y1)).line_to((x2, y2))),
+        );
+    }
+
+    fn polyline(&mut self, points: &[(f64, f64)], class: &str) {
+        self.document.append(
+            element::Polyline::new()
+                .set("class", class)
+                .set("points", points.to_vec()),
+        );
+    }
+
+    fn path(&mut self, commands: Vec<path::Command>, class: &str) {
+        self.document.append(
+            element::Path::new()
+                .set("class", class)
+                .set("d", path::Data::from(commands)),
+        );
+    }
+
+    fn text(&mut self, x: f64, y: f64, rotate: f64, text: &str, class: &str) {
+        self.document.append(
+            element::Text::new(text.to_owned())
+                .set("class", class)
+                .set("transform", Self::transform(x, y, rotate)),
+        );
+    }
+
+    fn finish(&mut self, width: f64, height: f64) -> Vec<u8> {
+        let document = std::mem::replace(&mut self.document, Document::new())
+            .set("width", width)
+            .set("height", height)
+            .set("viewBox", format!("0 0 {} {}", width, height));
+        let mut bytes = vec![];
+
+        svg::write(&mut bytes, &document).expect("svg serialization is infallible for a Vec<u8>");
+
+        bytes
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct StyleRule {
+    stroke: Option<Color>,
+    stroke_width: f32,
+    fill: Option<Color>,
+    fill_opacity: f32,
+}
+
+/// Pulls just enough out of the `.class{fill:...;stroke:...;stroke-width:...;}`
+/// rules `process_chart_data` generates to stroke or fill the equivalent shapes
+/// in a raster image; text styling (font, anchor) has no bitmap equivalent here.
+/// Alongside the rules, returns a warning for every declared `stroke`/`fill`
+/// color this backend couldn't understand, so callers can tell the user their
+/// color silently fell back to black/no-fill instead of guessing wrong.
+fn parse_styles(styles: &[String]) -> (HashMap<String, StyleRule>, Vec<String>) {
+    let mut rules = HashMap::new();
+    let mut warnings = vec![];
+
+    for style in styles {
+        let Some((selector, body)) = style.split_once('{') else {
+            continue;
+        };
+        let mut rule = StyleRule {
+            stroke_width: 1.0,
+            fill_opacity: 1.0,
+            ..Default::default()
+        };
+
+        for decl in body.trim_end_matches('}').split(';') {
+            let Some((key, value)) = decl.split_once(':') else {
+                continue;
+            };
+            let value = value.trim();
+
+            match key.trim() {
+                "stroke" => {
+                    rule.stroke = parse_color(value);
+
+                    if rule.stroke.is_none() && value != "none" {
+                        warnings.push(format!(
+                            "stroke color '{}' is not understood by PNG output, rendering as black",
+                            value
+                        ));
+                    }
+                }
+                "stroke-width" => rule.stroke_width = value.parse().unwrap_or(1.0),
+                "fill" => {
+                    rule.fill = parse_color(value);
+
+                    if rule.fill.is_none() && value != "none" {
+                        warnings.push(format!(
+                            "fill color '{}' is not understood by PNG output, skipping the fill",
+                            value
+                        ));
+                    }
+                }
+                "fill-opacity" => rule.fill_opacity = value.parse().unwrap_or(1.0),
+                _ => {}
+            }
+        }
+
+        rules.insert(selector.trim_start_matches('.').to_owned(), rule);
+    }
+
+    (rules, warnings)
+}
+
+/// Common CSS keyword colors a chart author might set as a series `color`.
+/// Not the full CSS named-color table, just the basic sixteen plus the handful
+/// (`orange`, `grey`) common enough to otherwise be a surprising gap.
+const NAMED_COLORS: &[(&str, (u8, u8, u8))] = &[
+    ("black", (0, 0, 0)),
+    ("silver", (192, 192, 192)),
+    ("gray", (128, 128, 128)),
+    ("grey", (128, 128, 128)),
+    ("white", (255, 255, 255)),
+    ("maroon", (128, 0, 0)),
+    ("red", (255, 0, 0)),
+    ("purple", (128, 0, 128)),
+    ("fuchsia", (255, 0, 255)),
+    ("magenta", (255, 0, 255)),
+    ("green", (0, 128, 0)),
+    ("lime", (0, 255, 0)),
+    ("olive", (128, 128, 0)),
+    ("yellow", (255, 255, 0)),
+    ("navy", (0, 0, 128)),
+    ("blue", (0, 0, 255)),
+    ("teal", (0, 128, 128)),
+    ("aqua", (0, 255, 255)),
+    ("cyan", (0, 255, 255)),
+    ("orange", (255, 165, 0)),
+];
+
+/// Parses the handful of CSS color forms `process_chart_data` can emit:
+/// `rgb(r,g,b)`, `#rrggbb`/`#rgb` hex, and the basic named colors. Anything
+/// else (hsl(), rgba(), the rest of the CSS named-color table, ...) is
+/// reported back to the caller as a warning rather than rendered wrong.
+fn parse_color(value: &str) -> Option<Color> {
+    if let Some(inner) = value.strip_prefix("rgb(").and_then(|s| s.strip_suffix(')')) {
+        let mut channels = inner.splitn(3, ',').map(|c| c.trim().parse::<u8>().ok());
+
+        return Some(Color::from_rgba8(
+            channels.next()??,
+            channels.next()??,
+            channels.next()??,
+            255,
+        ));
+    }
+
+    if let Some(hex) = value.strip_prefix('#') {
+        return parse_hex_color(hex);
+    }
+
+    NAMED_COLORS
+        .iter()
+        .find(|(name, _)| *name == value)
+        .map(|(_, (r, g, b))| Color::from_rgba8(*r, *g, *b, 255))
+}
+
+fn parse_hex_color(hex: &str) -> Option<Color> {
+    let expand = |c: char| u8::from_str_radix(&c.to_string().repeat(2), 16).ok();
+    let [r, g, b] = match hex.len() {
+        3 => {
+            let mut chars = hex.chars();
+
+            [
+                expand(chars.next()?)?,
+                expand(chars.next()?)?,
+                expand(chars.next()?)?,
+            ]
+        }
+        6 => {
+            let byte = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+
+            [byte(0)?, byte(2)?, byte(4)?]
+        }
+        _ => return None,
+    };
+
+    Some(Color::from_rgba8(r, g, b, 255))
+}
+
+/// Rasterizes the chart to a PNG `Pixmap`. Lines, polylines and paths are
+/// stroked with the colors from `set_styles`; text labels are not drawn since
+/// this backend has no font renderer available to it. Axis and x-axis tick
+/// positions are still marked with short rasterized ticks (see `text`) so the
+/// scale is at least visible without the labels themselves.
+pub(crate) struct PngBackend<'a> {
+    pixmap: Pixmap,
+    rules: HashMap<String, StyleRule>,
+    log: &'a dyn LineChartLog,
+}
+
+impl<'a> PngBackend<'a> {
+    pub(crate) fn new(width: f64, height: f64, log: &'a dyn LineChartLog) -> PngBackend<'a> {
+        PngBackend {
+            pixmap: Pixmap::new(width.ceil() as u32, height.ceil() as u32)
+                .expect("chart dimensions are always non-zero"),
+            rules: HashMap::new(),
+            log,
+        }
+    }
+
+    fn stroke_path(&mut self, builder: PathBuilder, class: &str) {
+        let Some(path) = builder.finish() else {
+            return;
+        };
+        let rule = self.rules.get(class).copied().unwrap_or_default();
+        let mut paint = Paint::default();
+
+        paint.set_color(rule.stroke.unwrap_or(Color::BLACK));
+        paint.anti_alias = true;
+
+        let stroke = Stroke {
+            width: if rule.stroke_width > 0.0 {
+                rule.stroke_width
+            } else {
+                1.0
+            },
+            ..Default::default()
+        };
+
+        self.pixmap
+            .stroke_path(&path, &paint, &stroke, Transform::identity(), None);
+    }
+
+    /// Fills a closed path with its class's declared fill color when it has
+    /// one (e.g. an area-chart `-area` class), otherwise falls back to
+    /// stroking it like any other shape.
+    fn fill_or_stroke_path(&mut self, builder: PathBuilder, class: &str) {
+        let rule = self.rules.get(class).copied().unwrap_or_default();
+        let Some(mut fill_color) = rule.fill else {
+            self.stroke_path(builder, class);
+            return;
+        };
+        let Some(path) = builder.finish() else {
+            return;
+        };
+        let mut paint = Paint::default();
+
+        fill_color.set_alpha(rule.fill_opacity);
+        paint.set_color(fill_color);
+        paint.anti_alias = true;
+
+        self.pixmap.fill_path(
+            &path,
+            &paint,
+            FillRule::Winding,
+            Transform::identity(),
+            None,
+        );
+    }
+
+    /// Draws a short `axis`-styled tick mark between two points, standing in
+    /// for a text label this backend can't render.
+    fn tick_mark(&mut self, x1: f64, y1: f64, x2: f64, y2: f64) {
+        let mut builder = PathBuilder::new();
+
+        builder.move_to(x1 as f32, y1 as f32);
+        builder.line_to(x2 as f32, y2 as f32);
+
+        self.stroke_path(builder, "axis");
+    }
+}
+
+impl<'a> ChartBackend for PngBackend<'a> {
+    fn set_styles(&mut self, styles: &[String]) {
+        let (rules, warnings) = parse_styles(styles);
+
+        for message in warnings {
+            warning!(self.log, "{}", message);
+        }
+
+        self.rules = rules;
+    }
+
+    fn line(&mut self, x1: f64, y1: f64, x2: f64, y2: f64, class: &str) {
+        let mut builder = PathBuilder::new();
+
+        builder.move_to(x1 as f32, y1 as f32);
+        builder.line_to(x2 as f32, y2 as f32);
+
+        self.stroke_path(builder, class);
+    }
+
+    fn polyline(&mut self, points: &[(f64, f64)], class: &str) {
+        let mut builder = PathBuilder::new();
+
+        for (i, (x, y)) in points.iter().enumerate() {
+            if i == 0 {
+                builder.move_to(*x as f32, *y as f32);
+            } else {
+                builder.line_to(*x as f32, *y as f32);
+            }
+        }
+
+        self.stroke_path(builder, class);
+    }
+
+    fn path(&mut self, commands: Vec<path::Command>, class: &str) {
+        let mut builder = PathBuilder::new();
+
+        for command in commands {
+            match command {
+                path::Command::Move(_, params) => builder.move_to(params[0], params[1]),
+                path::Command::Line(_, params) => builder.line_to(params[0], params[1]),
+                path::Command::CubicCurve(_, params) => builder.cubic_to(
+                    params[0], params[1], params[2], params[3], params[4], params[5],
+                ),
+                path::Command::Close => builder.close(),
+                _ => {}
+            }
+        }
+
+        self.fill_or_stroke_path(builder, class);
+    }
+
+    /// No font renderer is wired up for raster output yet, so the label text
+    /// itself never appears. For y-axis and x-axis tick labels, draws a short
+    /// tick mark at the label's position instead, so the chart's scale still
+    /// carries across as rasterized geometry even though the reader can't see
+    /// the actual tick values or the legend's series names.
+    fn text(&mut self, x: f64, y: f64, _rotate: f64, _text: &str, class: &str) {
+        if class.contains("y-labels") {
+            self.tick_mark(x + 6.0, y - 5.0, x + 10.0, y - 5.0);
+        } else if class == "labels" {
+            self.tick_mark(x, y - 15.0, x, y - 11.0);
+        }
+    }
+
+    fn finish(&mut self, _width: f64, _height: f64) -> Vec<u8> {
+        self.pixmap
+            .encode_png()
+            .expect("encoding an in-memory pixmap to PNG cannot fail")
+    }
+}
+
+/// Approximate size, in chart pixels, of one character cell. Terminal fonts
+/// are roughly twice as tall as they are wide, so a cell covers more y-pixels
+/// than x-pixels to keep the ASCII preview from looking squashed.
+const CELL_WIDTH: f64 = 8.0;
+const CELL_HEIGHT: f64 = 16.0;
+
+/// Picks a glyph for a path/line/polyline by class, so axis, series and
+/// error-bar strokes stay visually distinct without color.
+fn class_glyph(class: &str) -> char {
+    if class.contains("error-bar") {
+        '|'
+    } else if class.contains("axis") {
+        '.'
+    } else if class.ends_with("-area") {
+        ':'
+    } else {
+        '*'
+    }
+}
+
+/// Rasterizes the chart onto a character grid for a quick preview over SSH.
+/// Text labels are drawn as literal characters rather than skipped, since
+/// there's no font rendering problem to dodge here; curves and fills are
+/// approximated with straight Bresenham strokes at a coarse, single-glyph
+/// resolution.
+pub(crate) struct TerminalBackend {
+    grid: Vec<Vec<char>>,
+}
+
+impl TerminalBackend {
+    pub(crate) fn new(width: f64, height: f64) -> TerminalBackend {
+        let cols = ((width / CELL_WIDTH).ceil() as usize).max(1);
+        let rows = ((height / CELL_HEIGHT).ceil() as usize).max(1);
+
+        TerminalBackend {
+            grid: vec![vec![' '; cols]; rows],
+        }
+    }
+
+    fn cell(x: f64, y: f64) -> (i64, i64) {
+        (
+            (x / CELL_WIDTH).round() as i64,
+            (y / CELL_HEIGHT).round() as i64,
+        )
+    }
+
+    fn plot(&mut self, col: i64, row: i64, glyph: char) {
+        if col < 0 || row < 0 {
+            return;
+        }
+        if let Some(cell) = self
+            .grid
+            .get_mut(row as usize)
+            .and_then(|line| line.get_mut(col as usize))
+        {
+            *cell = glyph;
+        }
+    }
+
+    /// Bresenham's line algorithm between two cells, in terminal-grid units.
+    fn stroke_cells(&mut self, (x1, y1): (i64, i64), (x2, y2): (i64, i64), glyph: char) {
+        let (dx, dy) = ((x2 - x1).abs(), -(y2 - y1).abs());
+        let (sx, sy) = (if x1 < x2 { 1 } else { -1 }, if y1 < y2 { 1 } else { -1 });
+        let mut err = dx + dy;
+        let (mut x, mut y) = (x1, y1);
+
+        loop {
+            self.plot(x, y, glyph);
+
+            if x == x2 && y == y2 {
+                break;
+            }
+
+            let e2 = 2 * err;
+
+            if e2 >= dy {
+                err += dy;
+                x += sx;
+            }
+            if e2 <= dx {
+                err += dx;
+                y += sy;
+            }
+        }
+    }
+
+    fn stroke_path(&mut self, x1: f64, y1: f64, x2: f64, y2: f64, class: &str) {
+        let glyph = class_glyph(class);
+
+        self.stroke_cells(Self::cell(x1, y1), Self::cell(x2, y2), glyph);
+    }
+}
+
+impl ChartBackend for TerminalBackend {
+    fn set_styles(&mut self, _styles: &[String]) {
+        // Glyphs are derived from the class name directly; there's no CSS to parse.
+    }
+
+    fn line(&mut self, x1: f64, y1: f64, x2: f64, y2: f64, class: &str) {
+        self.stroke_path(x1, y1, x2, y2, class);
+    }
+
+    fn polyline(&mut self, points: &[(f64, f64)], class: &str) {
+        for pair in points.windows(2) {
+            self.stroke_path(pair[0].0, pair[0].1, pair[1].0, pair[1].1, class);
+        }
+    }
+
+    fn path(&mut self, commands: Vec<path::Command>, class: &str) {
+        let mut current = (0.0, 0.0);
+        let mut subpath_start = current;
+
+        for command in commands {
+            match command {
+                path::Command::Move(_, params) => {
+                    current = (params[0] as f64, params[1] as f64);
+                    subpath_start = current;
+                }
+                path::Command::Line(_, params) => {
+                    let next = (params[0] as f64, params[1] as f64);
+
+                    self.stroke_path(current.0, current.1, next.0, next.1, class);
+                    current = next;
+                }
+                path::Command::CubicCurve(_, params) => {
+                    let next = (params[4] as f64, params[5] as f64);
+
+                    self.stroke_path(current.0, current.1, next.0, next.1, class);
+                    current = next;
+                }
+                path::Command::Close => {
+                    self.stroke_path(
+                        current.0,
+                        current.1,
+                        subpath_start.0,
+                        subpath_start.1,
+                        class,
+                    );
+                    current = subpath_start;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn text(&mut self, x: f64, y: f64, _rotate: f64, text: &str, class: &str) {
+        let (col, row) = Self::cell(x, y);
+
+        if row < 0 {
+            return;
+        }
+
+        // The SVG backend right-anchors `.y-labels` text so it ends just before
+        // the axis; mirror that here instead of letting multi-digit labels run
+        // rightward into the axis and data columns.
+        let start_col = if class.contains("y-labels") {
+            col - (text.chars().count() as i64 - 1).max(0)
+        } else {
+            col
+        };
+
+        for (i, ch) in text.chars().enumerate() {
+            self.plot(start_col + i as i64, row, ch);
+        }
+    }
+
+    fn finish(&mut self, _width: f64, _height: f64) -> Vec<u8> {
+        self.grid
+            .iter()
+            .map(|row| row.iter().collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+            .into_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn terminal_backend_cell_converts_pixels_to_grid_coordinates() {
+        assert_eq!(TerminalBackend::cell(0.0, 0.0), (0, 0));
+        assert_eq!(TerminalBackend::cell(CELL_WIDTH, CELL_HEIGHT), (1, 1));
+        assert_eq!(
+            TerminalBackend::cell(CELL_WIDTH * 2.4, CELL_HEIGHT * 0.4),
+            (2, 0)
+        );
+    }
+
+    #[test]
+    fn terminal_backend_stroke_cells_draws_a_straight_horizontal_line() {
+        let mut backend = TerminalBackend::new(5.0 * CELL_WIDTH, 1.0 * CELL_HEIGHT);
+
+        backend.stroke_cells((0, 0), (3, 0), '*');
+
+        assert_eq!(backend.grid[0][..4], ['*', '*', '*', '*']);
+    }
+
+    #[test]
+    fn terminal_backend_plot_ignores_out_of_bounds_coordinates() {
+        let mut backend = TerminalBackend::new(2.0 * CELL_WIDTH, 2.0 * CELL_HEIGHT);
+
+        backend.plot(-1, 0, '*');
+        backend.plot(0, -1, '*');
+        backend.plot(100, 100, '*');
+
+        assert!(backend.grid.iter().all(|row| row.iter().all(|&c| c == ' ')));
+    }
+
+    #[test]
+    fn terminal_backend_right_anchors_y_label_text() {
+        let mut backend = TerminalBackend::new(10.0 * CELL_WIDTH, 2.0 * CELL_HEIGHT);
+
+        backend.text(5.0 * CELL_WIDTH, 0.0, 0.0, "123", "labels y-labels");
+
+        let row: String = backend.grid[0].iter().collect();
+
+        assert_eq!(&row[3..6], "123");
+    }
+
+    #[test]
+    fn terminal_backend_left_anchors_non_y_label_text() {
+        let mut backend = TerminalBackend::new(10.0 * CELL_WIDTH, 2.0 * CELL_HEIGHT);
+
+        backend.text(5.0 * CELL_WIDTH, 0.0, 0.0, "123", "labels");
+
+        let row: String = backend.grid[0].iter().collect();
+
+        assert_eq!(&row[5..8], "123");
+    }
+
+    #[test]
+    fn parse_color_reads_rgb_triples() {
+        assert_eq!(
+            parse_color("rgb(0,150,0)"),
+            Some(Color::from_rgba8(0, 150, 0, 255))
+        );
+        assert_eq!(
+            parse_color("rgb(255, 255, 255)"),
+            Some(Color::from_rgba8(255, 255, 255, 255))
+        );
+    }
+
+    #[test]
+    fn parse_color_reads_hex_triples() {
+        assert_eq!(
+            parse_color("#ff0080"),
+            Some(Color::from_rgba8(255, 0, 128, 255))
+        );
+        assert_eq!(
+            parse_color("#f08"),
+            Some(Color::from_rgba8(255, 0, 136, 255))
+        );
+    }
+
+    #[test]
+    fn parse_color_reads_named_colors() {
+        assert_eq!(parse_color("red"), Some(Color::from_rgba8(255, 0, 0, 255)));
+        assert_eq!(
+            parse_color("grey"),
+            Some(Color::from_rgba8(128, 128, 128, 255))
+        );
+    }
+
+    #[test]
+    fn parse_color_rejects_anything_else() {
+        assert_eq!(parse_color("none"), None);
+        assert_eq!(parse_color("hsl(0, 100%, 50%)"), None);
+        assert_eq!(parse_color(""), None);
+    }
+
+    #[test]
+    fn parse_styles_reads_stroke_and_fill_declarations() {
+        let styles = vec![".area{fill:rgb(0,0,200);fill-opacity:0.25;stroke:none;}".to_owned()];
+        let (rules, warnings) = parse_styles(&styles);
+        let rule = rules.get("area").expect("area rule should be present");
+
+        assert_eq!(rule.fill, Some(Color::from_rgba8(0, 0, 200, 255)));
+        assert_eq!(rule.fill_opacity, 0.25);
+        assert_eq!(rule.stroke, None);
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn parse_styles_defaults_stroke_width_and_fill_opacity() {
+        let styles = vec![".line{stroke:rgb(200,0,0);}".to_owned()];
+        let (rules, warnings) = parse_styles(&styles);
+        let rule = rules.get("line").expect("line rule should be present");
+
+        assert_eq!(rule.stroke, Some(Color::from_rgba8(200, 0, 0, 255)));
+        assert_eq!(rule.stroke_width, 1.0);
+        assert_eq!(rule.fill_opacity, 1.0);
+        assert!(warnings.is_empty());
+    }
+
+    struct TestLogger;
+
+    impl LineChartLog for TestLogger {
+        fn output(self: &Self, _args: core::fmt::Arguments) {}
+        fn warning(self: &Self, _args: core::fmt::Arguments) {}
+        fn error(self: &Self, _args: core::fmt::Arguments) {}
+    }
+
+    #[test]
+    fn png_backend_rasterizes_a_tick_mark_for_text_it_cannot_draw() {
+        let logger = TestLogger;
+        let mut backend = PngBackend::new(100.0, 100.0, &logger);
+
+        backend.set_styles(&[
+            ".axis{fill:none;stroke:rgb(0,0,0);stroke-width:1;}".to_owned(),
+        ]);
+        backend.text(50.0, 50.0, 0.0, "1.0", "labels y-labels");
+
+        let pixmap = backend.pixmap;
+        let has_mark = (0..pixmap.width())
+            .flat_map(|x| (0..pixmap.height()).map(move |y| (x, y)))
+            .any(|(x, y)| pixmap.pixel(x, y).map(|p| p.alpha() > 0).unwrap_or(false));
+
+        assert!(has_mark, "expected a rasterized tick mark, found none");
+    }
+
+    #[test]
+    fn png_backend_fills_an_area_path_with_its_declared_color_and_opacity() {
+        let logger = TestLogger;
+        let mut backend = PngBackend::new(20.0, 20.0, &logger);
+
+        backend.set_styles(&[
+            ".series-0-area{fill:rgb(0,0,200);fill-opacity:0.5;stroke:none;}".to_owned(),
+        ]);
+        backend.path(
+            vec![
+                path::Command::Move(path::Position::Absolute, path::Parameters::from((2.0, 2.0))),
+                path::Command::Line(
+                    path::Position::Absolute,
+                    path::Parameters::from((18.0, 2.0)),
+                ),
+                path::Command::Line(
+                    path::Position::Absolute,
+                    path::Parameters::from((18.0, 18.0)),
+                ),
+                path::Command::Line(path::Position::Absolute, path::Parameters::from((2.0, 18.0))),
+                path::Command::Close,
+            ],
+            "series-0-area",
+        );
+
+        let pixel = backend
+            .pixmap
+            .pixel(10, 10)
+            .expect("center of the filled square should be in bounds");
+
+        assert!(pixel.alpha() > 0, "expected the area to be filled");
+        assert!(
+            pixel.alpha() < 255,
+            "expected the declared fill-opacity to be applied, got alpha {}",
+            pixel.alpha()
+        );
+    }
+
+    #[test]
+    fn parse_styles_warns_on_unparseable_colors() {
+        let styles =
+            vec![".series-0{stroke:hsl(0, 100%, 50%);fill:not-a-color;}".to_owned()];
+        let (rules, warnings) = parse_styles(&styles);
+        let rule = rules.get("series-0").expect("rule should still be present");
+
+        assert_eq!(rule.stroke, None);
+        assert_eq!(rule.fill, None);
+        assert_eq!(warnings.len(), 2);
+    }
+}
@@ -1,5 +1,7 @@
+mod backend;
 mod log_macros;
 
+use backend::{ChartBackend, PngBackend, SvgBackend, TerminalBackend};
 use clap::Parser;
 use core::fmt::Arguments;
 use easy_error::{self, ResultExt};
@@ -10,7 +12,7 @@ use std::{
     io::{self, Read, Write},
     path::PathBuf,
 };
-use svg::{node::element::*, node::*, Document};
+use svg::node::element::path;
 
 pub trait LineChartLog {
     fn output(self: &Self, args: Arguments);
@@ -22,6 +24,12 @@ pub struct LineChartTool<'a> {
     log: &'a dyn LineChartLog,
 }
 
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum OutputFormat {
+    Svg,
+    Png,
+}
+
 #[derive(Parser)]
 #[clap(version, about, long_about = None)]
 struct Cli {
@@ -29,12 +37,49 @@ struct Cli {
     #[clap(value_name = "INPUT_FILE")]
     input_file: Option<PathBuf>,
 
-    /// The SVG output file
+    /// The output file, as SVG or PNG depending on its extension or --format
     #[clap(value_name = "OUTPUT_FILE")]
     output_file: Option<PathBuf>,
+
+    /// The output format, inferred from OUTPUT_FILE's extension when omitted
+    ///
+    /// PNG output has no font renderer, so the title, axis, and legend text
+    /// are not drawn; only the stroked/filled geometry and tick-position
+    /// marks carry across. Use SVG if you need the labels.
+    #[clap(long, value_enum)]
+    format: Option<OutputFormat>,
+
+    /// Force a logarithmic y-axis, overriding any `y_axis_scale` in the input
+    #[clap(long)]
+    log_y: bool,
+
+    /// Fill the area under every series, overriding any per-series `fill`
+    #[clap(long)]
+    area: bool,
+
+    /// Print an ASCII preview to the terminal instead of writing OUTPUT_FILE
+    #[clap(long)]
+    terminal: bool,
+
+    /// Draw a monotone cubic spline through each series, overriding `interpolation`
+    #[clap(long)]
+    smooth: bool,
 }
 
 impl Cli {
+    fn output_format(&self) -> OutputFormat {
+        if let Some(format) = self.format {
+            return format;
+        }
+
+        match self.output_file {
+            Some(ref path) if path.extension().and_then(|ext| ext.to_str()) == Some("png") => {
+                OutputFormat::Png
+            }
+            _ => OutputFormat::Svg,
+        }
+    }
+
     fn get_output(&self) -> Result<Box<dyn Write>, Box<dyn Error>> {
         match self.output_file {
             Some(ref path) => File::create(path)
@@ -63,6 +108,36 @@ impl Cli {
 pub struct ChartData {
     pub title: String,
     pub units: String,
+    #[serde(default)]
+    pub y_axis_scale: YAxisScale,
+    #[serde(default)]
+    pub interpolation: Interpolation,
+    pub series: Vec<Series>,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum YAxisScale {
+    #[default]
+    Linear,
+    Log,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum Interpolation {
+    #[default]
+    Linear,
+    Monotone,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct Series {
+    pub name: String,
+    pub color: Option<String>,
+    /// Fill the area between the line and the y-axis baseline.
+    #[serde(default)]
+    pub fill: bool,
     pub data: Vec<ItemData>,
 }
 
@@ -70,27 +145,208 @@ pub struct ChartData {
 pub struct ItemData {
     pub key: String,
     pub value: f64,
+    /// Symmetric measurement uncertainty, drawn as a capped bar spanning
+    /// `value - error ..= value + error`.
+    #[serde(default)]
+    pub error: Option<f64>,
+}
+
+/// Target number of y-axis ticks for the "nice numbers" interval search; the
+/// actual count varies since ticks must land on round values.
+const Y_AXIS_TARGET_TICKS: f64 = 8.0;
+
+/// Wilkinson/Heckbert "nice numbers": snaps `x` to the nearest value of the
+/// form `{1,2,5,10} * 10^exp`. Rounds to the nearest when `round` is set,
+/// otherwise rounds up so a range computed this way is never undershot.
+fn nicenum(x: f64, round: bool) -> f64 {
+    let exp = x.log10().floor();
+    let f = x / (10.0_f64).powf(exp);
+    let nf = if round {
+        if f < 1.5 {
+            1.0
+        } else if f < 3.0 {
+            2.0
+        } else if f < 7.0 {
+            5.0
+        } else {
+            10.0
+        }
+    } else if f <= 1.0 {
+        1.0
+    } else if f <= 2.0 {
+        2.0
+    } else if f <= 5.0 {
+        5.0
+    } else {
+        10.0
+    };
+
+    nf * (10.0_f64).powf(exp)
+}
+
+/// Rounds `value` to the number of decimal places implied by `interval`
+/// (e.g. an interval of `0.2` rounds to 1 decimal place), so ticks computed
+/// from repeated float addition don't print as `-0.3999999999999999`.
+fn round_to_interval(value: f64, interval: f64) -> f64 {
+    let decimals = (-interval.log10().floor()).max(0.0);
+    let factor = (10.0_f64).powf(decimals);
+
+    (value * factor).round() / factor
+}
+
+/// Fritsch-Carlson monotone cubic tangents for `points`, one per point. Interior
+/// tangents average the neighboring secant slopes, then are clamped (or zeroed
+/// across a local extremum) so the resulting spline never overshoots the data.
+fn monotone_tangents(points: &[(f64, f64)]) -> Vec<f64> {
+    let n = points.len();
+    let secants: Vec<f64> = (0..n - 1)
+        .map(|i| (points[i + 1].1 - points[i].1) / (points[i + 1].0 - points[i].0))
+        .collect();
+    let mut tangents = vec![0.0; n];
+
+    tangents[0] = secants[0];
+    tangents[n - 1] = secants[n - 2];
+
+    for i in 1..n - 1 {
+        tangents[i] = if secants[i - 1] == 0.0 || secants[i - 1].signum() != secants[i].signum() {
+            0.0
+        } else {
+            (secants[i - 1] + secants[i]) / 2.0
+        };
+    }
+
+    for i in 0..n - 1 {
+        if secants[i] == 0.0 {
+            tangents[i] = 0.0;
+            tangents[i + 1] = 0.0;
+            continue;
+        }
+
+        let alpha = tangents[i] / secants[i];
+        let beta = tangents[i + 1] / secants[i];
+        let h = alpha.hypot(beta);
+
+        if h > 3.0 {
+            let t = 3.0 / h;
+
+            tangents[i] = t * alpha * secants[i];
+            tangents[i + 1] = t * beta * secants[i];
+        }
+    }
+
+    tangents
+}
+
+/// Extends a traced line's commands down to `baseline_y` and back to its
+/// start, closing the shape so it can be filled as the area under the line.
+/// `first_x`/`last_x` are the line's first and last x-coordinates.
+fn close_area_path(
+    mut commands: Vec<path::Command>,
+    first_x: f64,
+    last_x: f64,
+    baseline_y: f64,
+) -> Vec<path::Command> {
+    commands.push(path::Command::Line(
+        path::Position::Absolute,
+        path::Parameters::from((last_x, baseline_y)),
+    ));
+    commands.push(path::Command::Line(
+        path::Position::Absolute,
+        path::Parameters::from((first_x, baseline_y)),
+    ));
+    commands.push(path::Command::Close);
+
+    commands
+}
+
+/// Builds the `Move`/`Line` (or `Move`/`CubicCurve`) commands tracing `points`
+/// in order. Falls back to straight segments unless `interpolation` asks for a
+/// monotone spline and there are enough points for one to mean anything.
+fn build_path_commands(points: &[(f64, f64)], interpolation: Interpolation) -> Vec<path::Command> {
+    let mut commands = Vec::with_capacity(points.len());
+    let Some(&first) = points.first() else {
+        return commands;
+    };
+
+    commands.push(path::Command::Move(
+        path::Position::Absolute,
+        path::Parameters::from(first),
+    ));
+
+    if interpolation == Interpolation::Monotone && points.len() >= 3 {
+        let tangents = monotone_tangents(points);
+
+        for i in 0..points.len() - 1 {
+            let (x0, y0) = points[i];
+            let (x1, y1) = points[i + 1];
+            let dx = (x1 - x0) / 3.0;
+
+            commands.push(path::Command::CubicCurve(
+                path::Position::Absolute,
+                path::Parameters::from((
+                    x0 + dx,
+                    y0 + tangents[i] * dx,
+                    x1 - dx,
+                    y1 - tangents[i + 1] * dx,
+                    x1,
+                    y1,
+                )),
+            ));
+        }
+    } else {
+        for &(x, y) in points.iter().skip(1) {
+            commands.push(path::Command::Line(
+                path::Position::Absolute,
+                path::Parameters::from((x, y)),
+            ));
+        }
+    }
+
+    commands
+}
+
+/// Stroke colors auto-assigned to series that don't specify their own `color`.
+const DEFAULT_SERIES_COLORS: &[&str] = &[
+    "rgb(0,0,200)",
+    "rgb(200,0,0)",
+    "rgb(0,150,0)",
+    "rgb(200,120,0)",
+    "rgb(150,0,150)",
+    "rgb(0,150,150)",
+];
+
+#[derive(Debug)]
+pub(crate) struct Gutter {
+    pub(crate) left: f64,
+    pub(crate) top: f64,
+    pub(crate) right: f64,
+    pub(crate) bottom: f64,
 }
 
 #[derive(Debug)]
-struct Gutter {
-    left: f64,
-    top: f64,
-    right: f64,
-    bottom: f64,
+pub(crate) struct RenderSeries {
+    pub(crate) name: String,
+    pub(crate) style_class: String,
+    pub(crate) fill: bool,
+    pub(crate) tuples: Vec<(String, f64, Option<f64>)>,
 }
 
 #[derive(Debug)]
-struct RenderData {
-    title: String,
-    units: String,
-    plot_width: f64,
-    y_axis_height: f64,
-    y_axis_range: (f64, f64),
-    y_axis_interval: f64,
-    gutter: Gutter,
-    styles: Vec<String>,
-    tuples: Vec<(String, f64)>,
+pub(crate) struct RenderData {
+    pub(crate) title: String,
+    pub(crate) units: String,
+    pub(crate) plot_width: f64,
+    pub(crate) y_axis_height: f64,
+    pub(crate) y_axis_scale: YAxisScale,
+    pub(crate) interpolation: Interpolation,
+    pub(crate) y_axis_range: (f64, f64),
+    /// Tick positions in data units, in ascending order; `true` marks a
+    /// labeled major tick (every linear tick, or a log decade boundary).
+    pub(crate) y_ticks: Vec<(f64, bool)>,
+    pub(crate) gutter: Gutter,
+    pub(crate) styles: Vec<String>,
+    pub(crate) x_labels: Vec<String>,
+    pub(crate) series: Vec<RenderSeries>,
 }
 
 impl<'a> LineChartTool<'a> {
@@ -110,11 +366,51 @@ impl<'a> LineChartTool<'a> {
             }
         };
 
-        let chart_data = Self::read_chart_file(cli.get_input()?)?;
+        let mut chart_data = Self::read_chart_file(cli.get_input()?)?;
+
+        if cli.log_y {
+            chart_data.y_axis_scale = YAxisScale::Log;
+        }
+
+        if cli.area {
+            for series in chart_data.series.iter_mut() {
+                series.fill = true;
+            }
+        }
+
+        if cli.smooth {
+            chart_data.interpolation = Interpolation::Monotone;
+        }
+
         let render_data = self.process_chart_data(&chart_data)?;
-        let document = self.render_chart(&render_data)?;
 
-        Self::write_svg_file(cli.get_output()?, &document)?;
+        if cli.terminal {
+            let (width, height) = Self::chart_dimensions(&render_data);
+            let preview =
+                self.render_chart(&render_data, &mut TerminalBackend::new(width, height))?;
+
+            for line in String::from_utf8_lossy(&preview).lines() {
+                output!(self.log, "{}", line);
+            }
+
+            return Ok(());
+        }
+
+        let bytes = match cli.output_format() {
+            OutputFormat::Svg => self.render_chart(&render_data, &mut SvgBackend::new())?,
+            OutputFormat::Png => {
+                warning!(
+                    self.log,
+                    "PNG output has no font renderer, so the title, axis, and legend text labels are not drawn (see --help)"
+                );
+
+                let (width, height) = Self::chart_dimensions(&render_data);
+
+                self.render_chart(&render_data, &mut PngBackend::new(width, height, self.log))?
+            }
+        };
+
+        Self::write_output_file(cli.get_output()?, &bytes)?;
 
         Ok(())
     }
@@ -129,38 +425,178 @@ impl<'a> LineChartTool<'a> {
         Ok(chart_data)
     }
 
-    fn write_svg_file(writer: Box<dyn Write>, document: &Document) -> Result<(), Box<dyn Error>> {
-        svg::write(writer, document)?;
+    fn write_output_file(mut writer: Box<dyn Write>, bytes: &[u8]) -> Result<(), Box<dyn Error>> {
+        writer.write_all(bytes)?;
 
         Ok(())
     }
 
     fn process_chart_data(self: &Self, cd: &ChartData) -> Result<RenderData, Box<dyn Error>> {
-        let mut tuples = vec![];
+        if cd.series.is_empty() {
+            return Err("Chart has no series to render".into());
+        }
+
         let mut y_axis_range: (f64, f64) = (f64::MAX, f64::MIN);
+        let x_labels: Vec<String> = cd
+            .series
+            .iter()
+            .max_by_key(|s| s.data.len())
+            .map(|s| s.data.iter().map(|i| i.key.to_owned()).collect())
+            .unwrap_or_default();
 
-        for item_data in cd.data.iter() {
-            let value = item_data.value;
+        if x_labels.is_empty() {
+            return Err("Chart's series have no data points to render".into());
+        }
+
+        for series in cd.series.iter() {
+            let keys: Vec<&String> = series.data.iter().map(|i| &i.key).collect();
+            let expected: Vec<&String> = x_labels.iter().collect();
+
+            if keys != expected {
+                return Err(format!(
+                    "Series '{}' has keys {:?}, which don't match the chart's x-axis keys {:?}",
+                    series.name, keys, x_labels
+                )
+                .into());
+            }
+        }
+
+        let mut styles = vec![
+            ".axis{fill:none;stroke:rgb(0,0,0);stroke-width:1;}".to_owned(),
+            ".labels{fill:rgb(0,0,0);font-size:10;font-family:Arial}".to_owned(),
+            ".y-labels{text-anchor:end;}".to_owned(),
+            ".title{font-family:Arial;font-size:12;text-anchor:middle;}".to_owned(),
+            ".legend{fill:rgb(0,0,0);font-size:10;font-family:Arial}".to_owned(),
+            ".error-bar{fill:none;stroke:rgb(0,0,0);stroke-width:1;}".to_owned(),
+        ];
+        let mut render_series = vec![];
+
+        for (i, series) in cd.series.iter().enumerate() {
+            let color = series.color.clone().unwrap_or_else(|| {
+                DEFAULT_SERIES_COLORS[i % DEFAULT_SERIES_COLORS.len()].to_owned()
+            });
+            let style_class = format!("series-{}", i);
+            let mut tuples = vec![];
+
+            for item_data in series.data.iter() {
+                let value = item_data.value;
+                let (low, high) = match item_data.error {
+                    Some(error) => (value - error, value + error),
+                    None => (value, value),
+                };
 
-            if value < y_axis_range.0 {
-                y_axis_range.0 = value;
-            } else if value > y_axis_range.1 {
-                y_axis_range.1 = value;
+                if low < y_axis_range.0 {
+                    y_axis_range.0 = low;
+                }
+                if high > y_axis_range.1 {
+                    y_axis_range.1 = high;
+                }
+
+                tuples.push((item_data.key.to_owned(), item_data.value, item_data.error));
+            }
+
+            styles.push(format!(
+                ".{}{{fill:none;stroke:{};stroke-width:2;}}",
+                style_class, color
+            ));
+
+            if series.fill {
+                styles.push(format!(
+                    ".{}-area{{fill:{};fill-opacity:0.25;stroke:none;}}",
+                    style_class, color
+                ));
             }
 
-            tuples.push((item_data.key.to_owned(), item_data.value));
+            render_series.push(RenderSeries {
+                name: series.name.to_owned(),
+                style_class,
+                fill: series.fill,
+                tuples,
+            });
         }
 
         let plot_width = 50.0;
         let y_axis_height = 400.0;
-        let y_axis_num_intervals = 20;
-        let y_axis_interval = (10.0_f64).powf(((y_axis_range.1 - y_axis_range.0).log10()).ceil())
-            / (y_axis_num_intervals as f64);
 
-        y_axis_range = (
-            f64::floor(y_axis_range.0 / y_axis_interval) * y_axis_interval,
-            f64::ceil(y_axis_range.1 / y_axis_interval) * y_axis_interval,
-        );
+        let (y_axis_range, y_ticks) = match cd.y_axis_scale {
+            YAxisScale::Linear => {
+                if y_axis_range.1 - y_axis_range.0 == 0.0 {
+                    let pad = if y_axis_range.0 == 0.0 {
+                        1.0
+                    } else {
+                        y_axis_range.0.abs() * 0.05
+                    };
+
+                    y_axis_range = (y_axis_range.0 - pad, y_axis_range.1 + pad);
+                }
+
+                let y_axis_interval = nicenum(
+                    nicenum(y_axis_range.1 - y_axis_range.0, false) / (Y_AXIS_TARGET_TICKS - 1.0),
+                    true,
+                );
+
+                y_axis_range = (
+                    f64::floor(y_axis_range.0 / y_axis_interval) * y_axis_interval,
+                    f64::ceil(y_axis_range.1 / y_axis_interval) * y_axis_interval,
+                );
+
+                let num_intervals = ((y_axis_range.1 - y_axis_range.0) / y_axis_interval) as usize;
+                let ticks = (0..=num_intervals)
+                    .map(|i| {
+                        (
+                            round_to_interval(
+                                y_axis_range.0 + i as f64 * y_axis_interval,
+                                y_axis_interval,
+                            ),
+                            true,
+                        )
+                    })
+                    .collect();
+
+                (y_axis_range, ticks)
+            }
+            YAxisScale::Log => {
+                for series in cd.series.iter() {
+                    for item_data in series.data.iter() {
+                        let low = item_data.value - item_data.error.unwrap_or(0.0);
+
+                        if low <= 0.0 {
+                            return Err(format!(
+                                "Logarithmic y-axis requires positive values, but '{}' is {}",
+                                item_data.key, low
+                            )
+                            .into());
+                        }
+                    }
+                }
+
+                let mut decade_min = y_axis_range.0.log10().floor() as i32;
+                let mut decade_max = y_axis_range.1.log10().ceil() as i32;
+
+                if decade_min == decade_max {
+                    decade_min -= 1;
+                    decade_max += 1;
+                }
+
+                let mut ticks = vec![];
+
+                for decade in decade_min..=decade_max {
+                    let base = (10.0_f64).powi(decade);
+
+                    ticks.push((base, true));
+
+                    if decade < decade_max {
+                        for minor in 2..=9 {
+                            ticks.push((base * (minor as f64), false));
+                        }
+                    }
+                }
+
+                let range = ((10.0_f64).powi(decade_min), (10.0_f64).powi(decade_max));
+
+                (range, ticks)
+            }
+        };
 
         let gutter = Gutter {
             top: 40.0,
@@ -174,111 +610,170 @@ impl<'a> LineChartTool<'a> {
             units: cd.units.to_owned(),
             plot_width,
             y_axis_height,
+            y_axis_scale: cd.y_axis_scale,
+            interpolation: cd.interpolation,
             y_axis_range,
-            y_axis_interval,
+            y_ticks,
             gutter,
-            styles: vec![
-                ".line{fill:none;stroke:rgb(0,0,200);stroke-width:2;}".to_owned(),
-                ".axis{fill:none;stroke:rgb(0,0,0);stroke-width:1;}".to_owned(),
-                ".labels{fill:rgb(0,0,0);font-size:10;font-family:Arial}".to_owned(),
-                ".y-labels{text-anchor:end;}".to_owned(),
-                ".title{font-family:Arial;font-size:12;text-anchor:middle;}".to_owned(),
-            ],
-            tuples,
+            styles,
+            x_labels,
+            series: render_series,
         })
     }
 
-    fn render_chart(self: &Self, rd: &RenderData) -> Result<Document, Box<dyn Error>> {
-        let width = rd.gutter.left + ((rd.tuples.len() as f64) * rd.plot_width) + rd.gutter.right;
+    fn chart_dimensions(rd: &RenderData) -> (f64, f64) {
+        let width = rd.gutter.left + ((rd.x_labels.len() as f64) * rd.plot_width) + rd.gutter.right;
         let height = rd.gutter.top + rd.gutter.bottom + rd.y_axis_height;
-        let y_range = ((rd.y_axis_range.1 - rd.y_axis_range.0) / rd.y_axis_interval) as usize;
-        let y_scale = rd.y_axis_height / (rd.y_axis_range.1 - rd.y_axis_range.0);
+
+        (width, height)
+    }
+
+    fn render_chart(
+        self: &Self,
+        rd: &RenderData,
+        backend: &mut dyn ChartBackend,
+    ) -> Result<Vec<u8>, Box<dyn Error>> {
+        let (width, height) = Self::chart_dimensions(rd);
+        let normalize = |n: f64| -> f64 {
+            match rd.y_axis_scale {
+                YAxisScale::Linear => {
+                    (n - rd.y_axis_range.0) / (rd.y_axis_range.1 - rd.y_axis_range.0)
+                }
+                YAxisScale::Log => {
+                    (n.log10() - rd.y_axis_range.0.log10())
+                        / (rd.y_axis_range.1.log10() - rd.y_axis_range.0.log10())
+                }
+            }
+        };
         let scale =
-            |n: &f64| -> f64 { height - rd.gutter.bottom - (n - rd.y_axis_range.0) * y_scale };
-        let mut document = Document::new()
-            .set("xmlns", "http://www.w3.org/2000/svg")
-            .set("width", width)
-            .set("height", height)
-            .set("viewBox", format!("0 0 {} {}", width, height))
-            .set("style", "background-color: white;");
-        let style = element::Style::new(rd.styles.join("\n"));
-        let axis = element::Polyline::new().set("class", "axis").set(
-            "points",
-            vec![
+            |n: &f64| -> f64 { height - rd.gutter.bottom - normalize(*n) * rd.y_axis_height };
+
+        backend.set_styles(&rd.styles);
+        backend.polyline(
+            &[
                 (rd.gutter.left, rd.gutter.top),
                 (rd.gutter.left, rd.gutter.top + rd.y_axis_height),
                 (width - rd.gutter.right, rd.gutter.top + rd.y_axis_height),
             ],
+            "axis",
         );
-        let mut x_axis_labels = element::Group::new().set("class", "labels");
-
-        for i in 0..rd.tuples.len() {
-            x_axis_labels.append(element::Text::new(format!("{}", rd.tuples[i].0)).set(
-                "transform",
-                format!(
-                    "translate({},{}) rotate(45)",
-                    rd.gutter.left + (i as f64 * rd.plot_width) + rd.plot_width / 2.0,
-                    height - rd.gutter.bottom + 15.0
-                ),
-            ));
+
+        for (i, label) in rd.x_labels.iter().enumerate() {
+            backend.text(
+                rd.gutter.left + (i as f64 * rd.plot_width) + rd.plot_width / 2.0,
+                height - rd.gutter.bottom + 15.0,
+                45.0,
+                label,
+                "labels",
+            );
+        }
+
+        for (value, is_major) in rd.y_ticks.iter() {
+            let y = scale(value);
+
+            if *is_major {
+                backend.text(
+                    rd.gutter.left - 10.0,
+                    y + 5.0,
+                    0.0,
+                    &format!("{}", value),
+                    "labels y-labels",
+                );
+            } else {
+                backend.line(rd.gutter.left - 4.0, y, rd.gutter.left, y, "axis");
+            }
         }
 
-        let mut y_axis_labels = element::Group::new().set("class", "labels y-labels");
+        for series in rd.series.iter() {
+            let points: Vec<(f64, f64)> = series
+                .tuples
+                .iter()
+                .enumerate()
+                .map(|(i, (_, value, _))| {
+                    let x = rd.gutter.left + (i as f64) * rd.plot_width + rd.plot_width / 2.0;
 
-        for i in 0..=y_range {
-            let n = i as f64 * rd.y_axis_interval;
+                    (x, scale(value))
+                })
+                .collect();
+            let commands = build_path_commands(&points, rd.interpolation);
 
-            y_axis_labels.append(
-                element::Text::new(format!("{}", n + rd.y_axis_range.0)).set(
-                    "transform",
-                    format!(
-                        "translate({},{})",
-                        rd.gutter.left - 10.0,
-                        height - rd.gutter.bottom - f64::floor(n * y_scale) + 5.0
-                    ),
-                ),
-            );
+            if series.fill && !series.tuples.is_empty() {
+                let baseline_y = scale(&rd.y_axis_range.0);
+                let last_x = rd.gutter.left
+                    + ((series.tuples.len() - 1) as f64) * rd.plot_width
+                    + rd.plot_width / 2.0;
+                let first_x = rd.gutter.left + rd.plot_width / 2.0;
+                let area_commands = close_area_path(commands.clone(), first_x, last_x, baseline_y);
+
+                backend.path(area_commands, &format!("{}-area", series.style_class));
+            }
+
+            backend.path(commands, &series.style_class);
+
+            let cap = 4.0;
+
+            for (i, (_, value, error)) in series.tuples.iter().enumerate() {
+                let Some(error) = error else { continue };
+                let x = rd.gutter.left + (i as f64) * rd.plot_width + rd.plot_width / 2.0;
+                let y_low = scale(&(value - error));
+                let y_high = scale(&(value + error));
+
+                backend.path(
+                    vec![
+                        path::Command::Move(
+                            path::Position::Absolute,
+                            path::Parameters::from((x, y_high)),
+                        ),
+                        path::Command::Line(
+                            path::Position::Absolute,
+                            path::Parameters::from((x, y_low)),
+                        ),
+                        path::Command::Move(
+                            path::Position::Absolute,
+                            path::Parameters::from((x - cap, y_high)),
+                        ),
+                        path::Command::Line(
+                            path::Position::Absolute,
+                            path::Parameters::from((x + cap, y_high)),
+                        ),
+                        path::Command::Move(
+                            path::Position::Absolute,
+                            path::Parameters::from((x - cap, y_low)),
+                        ),
+                        path::Command::Line(
+                            path::Position::Absolute,
+                            path::Parameters::from((x + cap, y_low)),
+                        ),
+                    ],
+                    "error-bar",
+                );
+            }
         }
 
-        let line = element::Path::new().set("class", "line").set(
-            "d",
-            path::Data::from(
-                rd.tuples
-                    .iter()
-                    .enumerate()
-                    .map(|t| {
-                        let x = rd.gutter.left + (t.0 as f64) * rd.plot_width + rd.plot_width / 2.0;
-                        let y = scale(&(*t.1).1);
-
-                        if t.0 == 0 {
-                            path::Command::Move(
-                                path::Position::Absolute,
-                                path::Parameters::from((x, y)),
-                            )
-                        } else {
-                            path::Command::Line(
-                                path::Position::Absolute,
-                                path::Parameters::from((x, y)),
-                            )
-                        }
-                    })
-                    .collect::<Vec<_>>(),
-            ),
+        backend.text(
+            width / 2.0,
+            rd.gutter.top / 2.0,
+            0.0,
+            &format!("{} ({})", &rd.title, &rd.units),
+            "title",
         );
 
-        let title = element::Text::new(format!("{} ({})", &rd.title, &rd.units))
-            .set("class", "title")
-            .set("x", width / 2.0)
-            .set("y", rd.gutter.top / 2.0);
+        self.render_legend(rd, width, backend);
+
+        Ok(backend.finish(width, height))
+    }
+
+    fn render_legend(self: &Self, rd: &RenderData, width: f64, backend: &mut dyn ChartBackend) {
+        let swatch_size = 10.0;
+        let row_height = 14.0;
 
-        document.append(style);
-        document.append(axis);
-        document.append(x_axis_labels);
-        document.append(y_axis_labels);
-        document.append(line);
-        document.append(title);
+        for (i, series) in rd.series.iter().enumerate() {
+            let y = rd.gutter.top + (i as f64) * row_height;
+            let x = width - rd.gutter.right + 10.0;
 
-        Ok(document)
+            backend.line(x, y, x + swatch_size, y, &series.style_class);
+            backend.text(x + swatch_size + 4.0, y + 3.0, 0.0, &series.name, "legend");
+        }
     }
 }
 
@@ -286,26 +781,295 @@ impl<'a> LineChartTool<'a> {
 mod tests {
     use super::*;
 
-    #[test]
-    fn basic_test() {
-        struct TestLogger;
+    struct TestLogger;
 
-        impl TestLogger {
-            fn new() -> TestLogger {
-                TestLogger {}
-            }
+    impl TestLogger {
+        fn new() -> TestLogger {
+            TestLogger {}
+        }
+    }
+
+    impl LineChartLog for TestLogger {
+        fn output(self: &Self, _args: Arguments) {}
+        fn warning(self: &Self, _args: Arguments) {}
+        fn error(self: &Self, _args: Arguments) {}
+    }
+
+    /// Builds a minimal `ChartData` around the given series, for tests that
+    /// only care about `process_chart_data`'s handling of `y_axis_scale` and
+    /// series/data-point content.
+    fn chart_with(y_axis_scale: YAxisScale, series: Vec<Series>) -> ChartData {
+        ChartData {
+            title: "Test".into(),
+            units: "".into(),
+            y_axis_scale,
+            interpolation: Interpolation::Linear,
+            series,
+        }
+    }
+
+    fn series_with(name: &str, data: Vec<ItemData>) -> Series {
+        Series {
+            name: name.into(),
+            color: None,
+            fill: false,
+            data,
         }
+    }
 
-        impl LineChartLog for TestLogger {
-            fn output(self: &Self, _args: Arguments) {}
-            fn warning(self: &Self, _args: Arguments) {}
-            fn error(self: &Self, _args: Arguments) {}
+    fn item(key: &str, value: f64) -> ItemData {
+        ItemData {
+            key: key.into(),
+            value,
+            error: None,
         }
+    }
 
+    fn item_with_error(key: &str, value: f64, error: f64) -> ItemData {
+        ItemData {
+            key: key.into(),
+            value,
+            error: Some(error),
+        }
+    }
+
+    #[test]
+    fn basic_test() {
         let logger = TestLogger::new();
         let mut tool = LineChartTool::new(&logger);
         let args: Vec<std::ffi::OsString> = vec!["".into(), "--help".into()];
 
         tool.run(args).unwrap();
     }
+
+    #[test]
+    fn nicenum_snaps_to_1_2_5_10() {
+        assert_eq!(nicenum(1.3, true), 1.0);
+        assert_eq!(nicenum(2.4, true), 2.0);
+        assert_eq!(nicenum(4.0, true), 5.0);
+        assert_eq!(nicenum(8.0, true), 10.0);
+
+        assert_eq!(nicenum(0.6, false), 1.0);
+        assert_eq!(nicenum(1.2, false), 2.0);
+        assert_eq!(nicenum(3.0, false), 5.0);
+        assert_eq!(nicenum(7.0, false), 10.0);
+    }
+
+    #[test]
+    fn nicenum_preserves_order_of_magnitude() {
+        assert_eq!(nicenum(130.0, true), 100.0);
+        assert_eq!(nicenum(0.013, true), 0.01);
+    }
+
+    #[test]
+    fn y_axis_ticks_print_as_clean_decimals_for_a_flat_series() {
+        let logger = TestLogger::new();
+        let tool = LineChartTool::new(&logger);
+        let chart_data = chart_with(
+            YAxisScale::Linear,
+            vec![series_with("s", vec![item("a", 5.0), item("b", 5.0)])],
+        );
+
+        let render_data = tool.process_chart_data(&chart_data).unwrap();
+
+        for (value, _) in render_data.y_ticks.iter() {
+            let label = format!("{}", value);
+
+            assert!(
+                !label.contains("00000"),
+                "tick {} printed as {}",
+                value,
+                label
+            );
+        }
+    }
+
+    #[test]
+    fn y_axis_range_widens_to_cover_a_points_error_bar() {
+        let logger = TestLogger::new();
+        let tool = LineChartTool::new(&logger);
+        let chart_data = chart_with(
+            YAxisScale::Linear,
+            vec![series_with("s", vec![item_with_error("a", 10.0, 50.0)])],
+        );
+
+        let render_data = tool.process_chart_data(&chart_data).unwrap();
+
+        assert!(render_data.y_axis_range.0 <= -40.0);
+        assert!(render_data.y_axis_range.1 >= 60.0);
+    }
+
+    #[test]
+    fn process_chart_data_rejects_a_chart_with_no_series() {
+        let logger = TestLogger::new();
+        let tool = LineChartTool::new(&logger);
+        let chart_data = chart_with(YAxisScale::Linear, vec![]);
+
+        assert!(tool.process_chart_data(&chart_data).is_err());
+    }
+
+    #[test]
+    fn process_chart_data_rejects_a_series_with_no_data_points() {
+        let logger = TestLogger::new();
+        let tool = LineChartTool::new(&logger);
+        let chart_data = chart_with(YAxisScale::Linear, vec![series_with("s", vec![])]);
+
+        assert!(tool.process_chart_data(&chart_data).is_err());
+    }
+
+    #[test]
+    fn process_chart_data_rejects_a_series_whose_keys_dont_match_the_x_axis() {
+        let logger = TestLogger::new();
+        let tool = LineChartTool::new(&logger);
+        let chart_data = chart_with(
+            YAxisScale::Linear,
+            vec![
+                series_with("a", vec![item("x", 1.0), item("y", 2.0)]),
+                series_with("b", vec![item("x", 1.0), item("z", 2.0)]),
+            ],
+        );
+
+        assert!(tool.process_chart_data(&chart_data).is_err());
+    }
+
+    #[test]
+    fn log_y_axis_widens_a_degenerate_single_decade_range() {
+        let logger = TestLogger::new();
+        let tool = LineChartTool::new(&logger);
+        let chart_data = chart_with(YAxisScale::Log, vec![series_with("s", vec![item("a", 10.0)])]);
+
+        let render_data = tool.process_chart_data(&chart_data).unwrap();
+
+        assert_eq!(render_data.y_axis_range, (1.0, 100.0));
+    }
+
+    #[test]
+    fn log_y_axis_rejects_a_value_whose_error_bar_dips_non_positive() {
+        let logger = TestLogger::new();
+        let tool = LineChartTool::new(&logger);
+        let chart_data = chart_with(
+            YAxisScale::Log,
+            vec![series_with("s", vec![item_with_error("a", 1.0, 2.0)])],
+        );
+
+        assert!(tool.process_chart_data(&chart_data).is_err());
+    }
+
+    #[test]
+    fn log_y_axis_generates_major_and_minor_ticks_across_decades() {
+        let logger = TestLogger::new();
+        let tool = LineChartTool::new(&logger);
+        let chart_data = chart_with(
+            YAxisScale::Log,
+            vec![series_with("s", vec![item("a", 5.0), item("b", 500.0)])],
+        );
+
+        let render_data = tool.process_chart_data(&chart_data).unwrap();
+
+        assert_eq!(render_data.y_axis_range, (1.0, 1000.0));
+
+        let major_ticks: Vec<f64> = render_data
+            .y_ticks
+            .iter()
+            .filter(|(_, is_major)| *is_major)
+            .map(|(value, _)| *value)
+            .collect();
+
+        assert_eq!(major_ticks, vec![1.0, 10.0, 100.0, 1000.0]);
+        assert!(render_data
+            .y_ticks
+            .iter()
+            .any(|(value, is_major)| *value == 50.0 && !is_major));
+    }
+
+    #[test]
+    fn monotone_tangents_follow_the_slope_of_a_straight_line() {
+        let points = [(0.0, 0.0), (1.0, 1.0), (2.0, 2.0), (3.0, 3.0)];
+        let tangents = monotone_tangents(&points);
+
+        assert_eq!(tangents, vec![1.0, 1.0, 1.0, 1.0]);
+    }
+
+    #[test]
+    fn monotone_tangents_are_zeroed_across_a_local_extremum() {
+        let points = [(0.0, 0.0), (1.0, 1.0), (2.0, 0.0)];
+        let tangents = monotone_tangents(&points);
+
+        assert_eq!(tangents[1], 0.0);
+    }
+
+    #[test]
+    fn build_path_commands_uses_lines_for_linear_interpolation() {
+        let points = [(0.0, 0.0), (1.0, 1.0), (2.0, 0.0)];
+        let commands = build_path_commands(&points, Interpolation::Linear);
+
+        assert_eq!(commands.len(), 3);
+        assert!(matches!(
+            commands[0],
+            path::Command::Move(path::Position::Absolute, _)
+        ));
+        assert!(matches!(
+            commands[1],
+            path::Command::Line(path::Position::Absolute, _)
+        ));
+        assert!(matches!(
+            commands[2],
+            path::Command::Line(path::Position::Absolute, _)
+        ));
+    }
+
+    #[test]
+    fn build_path_commands_uses_curves_for_monotone_interpolation() {
+        let points = [(0.0, 0.0), (1.0, 1.0), (2.0, 0.0)];
+        let commands = build_path_commands(&points, Interpolation::Monotone);
+
+        assert_eq!(commands.len(), 3);
+        assert!(matches!(
+            commands[1],
+            path::Command::CubicCurve(path::Position::Absolute, _)
+        ));
+        assert!(matches!(
+            commands[2],
+            path::Command::CubicCurve(path::Position::Absolute, _)
+        ));
+    }
+
+    #[test]
+    fn close_area_path_extends_the_line_down_to_the_baseline_and_back() {
+        let points = [(0.0, 10.0), (1.0, 5.0), (2.0, 10.0)];
+        let commands = build_path_commands(&points, Interpolation::Linear);
+        let area_commands = close_area_path(commands.clone(), 0.0, 2.0, 20.0);
+
+        assert_eq!(area_commands.len(), commands.len() + 3);
+
+        match &area_commands[commands.len()] {
+            path::Command::Line(path::Position::Absolute, params) => {
+                assert_eq!((params[0], params[1]), (2.0, 20.0));
+            }
+            _ => panic!("expected a line down to the baseline"),
+        }
+
+        match &area_commands[commands.len() + 1] {
+            path::Command::Line(path::Position::Absolute, params) => {
+                assert_eq!((params[0], params[1]), (0.0, 20.0));
+            }
+            _ => panic!("expected a line back to the start"),
+        }
+
+        assert!(matches!(
+            area_commands[commands.len() + 2],
+            path::Command::Close
+        ));
+    }
+
+    #[test]
+    fn build_path_commands_falls_back_to_lines_below_three_points() {
+        let points = [(0.0, 0.0), (1.0, 1.0)];
+        let commands = build_path_commands(&points, Interpolation::Monotone);
+
+        assert!(matches!(
+            commands[1],
+            path::Command::Line(path::Position::Absolute, _)
+        ));
+    }
 }